@@ -1,5 +1,5 @@
 use crate::{AmiError, BinaryOp, Node, NodeType, Token, TokenType, UnaryOp};
-use std::{iter::Peekable, vec::IntoIter};
+use std::{fmt, iter::Peekable, ops::Range, vec::IntoIter};
 
 use TokenType::*;
 
@@ -10,6 +10,162 @@ pub struct Parser {
 
 type ParseResult = Result<Node, AmiError>;
 
+/// Something `atom`/`ternary` were willing to accept at the point of
+/// failure: either a concrete token, or (for positions like "start of an
+/// atom" where a bare token list can't say "a number" or "a variable") a
+/// human-readable description.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    Token(TokenType),
+    Description(&'static str),
+}
+
+impl From<TokenType> for Expected {
+    fn from(ty: TokenType) -> Self {
+        Expected::Token(ty)
+    }
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Token(ty) => write!(f, "{}", ty),
+            Expected::Description(desc) => write!(f, "{}", desc),
+        }
+    }
+}
+
+/// Joins `items` the way the pre-refactor error messages did: a lone item
+/// stands alone, two are joined with a bare `or`, and three or more use an
+/// Oxford comma (`a, b, or c`).
+fn join_expected(items: &[Expected]) -> String {
+    match items {
+        [] => String::new(),
+        [only] => only.to_string(),
+        [first, second] => format!("{} or {}", first, second),
+        [init @ .., last] => {
+            let init: Vec<String> = init.iter().map(Expected::to_string).collect();
+            format!("{}, or {}", init.join(", "), last)
+        }
+    }
+}
+
+/// Structured parse-error kinds, carried inside [`AmiError`] so callers can
+/// match on the kind of failure instead of scraping the formatted message.
+/// Each variant exposes its source span via [`ExprError::span`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprError {
+    UnexpectedToken {
+        found: TokenType,
+        expected: Vec<Expected>,
+        span: Range<usize>,
+    },
+    UnmatchedDelimiter {
+        opener: TokenType,
+        expected_closer: TokenType,
+        span: Range<usize>,
+    },
+    UnexpectedEof {
+        span: Range<usize>,
+    },
+}
+
+impl ExprError {
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            ExprError::UnexpectedToken { span, .. } => span.clone(),
+            ExprError::UnmatchedDelimiter { span, .. } => span.clone(),
+            ExprError::UnexpectedEof { span } => span.clone(),
+        }
+    }
+
+    fn msg(&self) -> &'static str {
+        match self {
+            ExprError::UnexpectedToken { .. } | ExprError::UnmatchedDelimiter { .. } => {
+                "expected token"
+            }
+            ExprError::UnexpectedEof { .. } => "unexpected end of input",
+        }
+    }
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExprError::UnexpectedToken { expected, .. } => {
+                write!(f, "expected {}", join_expected(expected))
+            }
+            ExprError::UnmatchedDelimiter {
+                expected_closer, ..
+            } => write!(f, "expected {}", expected_closer),
+            ExprError::UnexpectedEof { .. } => write!(f, "the expression ended unexpectedly"),
+        }
+    }
+}
+
+impl From<ExprError> for AmiError {
+    fn from(err: ExprError) -> Self {
+        AmiError {
+            msg: err.msg().to_string(),
+            reason: err.to_string(),
+            range: err.span(),
+        }
+    }
+}
+
+/// Left/right binding power of an infix operator. `left_bp < right_bp`
+/// makes the operator left-associative (a run of same-precedence infixes
+/// folds left-to-right); `left_bp > right_bp` makes it right-associative.
+/// Logical operators bind loosest, then comparisons, then arithmetic — the
+/// ternary in [`Parser::ternary`] sits below all of these.
+fn infix_binding_power(ty: &TokenType) -> Option<(BinaryOp, u8, u8)> {
+    Some(match ty {
+        Or => (BinaryOp::Or, 1, 2),
+        And => (BinaryOp::And, 3, 4),
+        Lt => (BinaryOp::Lt, 5, 6),
+        Le => (BinaryOp::Le, 5, 6),
+        EqEq => (BinaryOp::Eq, 5, 6),
+        Ne => (BinaryOp::Ne, 5, 6),
+        Ge => (BinaryOp::Ge, 5, 6),
+        Gt => (BinaryOp::Gt, 5, 6),
+        Plus => (BinaryOp::Add, 7, 8),
+        Minus => (BinaryOp::Sub, 7, 8),
+        Star | Dot | Cross => (BinaryOp::Mul, 9, 10),
+        Slash | Divide => (BinaryOp::Div, 9, 10),
+        Percent | Mod => (BinaryOp::Mod, 9, 10),
+        Carrot => (BinaryOp::Pow, 14, 13),
+        _ => return None,
+    })
+}
+
+/// Binding power a prefix operator uses to parse its own operand.
+fn prefix_binding_power(ty: &TokenType) -> Option<(UnaryOp, u8)> {
+    Some(match ty {
+        Plus => (UnaryOp::Pos, 11),
+        Minus => (UnaryOp::Neg, 11),
+        Sqrt => (UnaryOp::Sqrt, 15),
+        Cbrt => (UnaryOp::Cbrt, 15),
+        Fort => (UnaryOp::Fort, 15),
+        _ => return None,
+    })
+}
+
+/// Minimum binding power a postfix operator requires of the expression
+/// it's attaching to; checked in the same loop as infix operators.
+fn postfix_binding_power(ty: &TokenType) -> Option<(UnaryOp, u8)> {
+    Some(match ty {
+        Exclamation => (UnaryOp::Fact, 16),
+        Degree => (UnaryOp::Degree, 16),
+        _ => return None,
+    })
+}
+
+/// Binding power [`Parser::ternary`] parses its condition and branches at:
+/// equal to the lowest left binding power in the table above (`||`'s 1),
+/// so every infix operator is still consumed there and `?:` is looser
+/// than `||`.
+const TERNARY_BP: u8 = 1;
+
 impl Parser {
     pub fn new(tokens: Vec<Token>) -> Self {
         let mut iter = tokens.into_iter().peekable();
@@ -43,12 +199,34 @@ impl Parser {
         })
     }
 
-    fn error<T>(&self, msg: String, reason: String, start: usize) -> Result<T, AmiError> {
-        Err(AmiError {
-            msg,
-            reason,
-            range: start..self.token.range.end,
-        })
+    fn unexpected(&self, expected: Vec<Expected>, start: usize) -> Result<Node, AmiError> {
+        if self.token.ty == EOF {
+            return Err(ExprError::UnexpectedEof {
+                span: start..self.token.range.end,
+            }
+            .into());
+        }
+
+        Err(ExprError::UnexpectedToken {
+            found: self.token.ty.clone(),
+            expected,
+            span: start..self.token.range.end,
+        }
+        .into())
+    }
+
+    fn unmatched_delimiter(
+        &self,
+        opener: TokenType,
+        expected_closer: TokenType,
+        start: usize,
+    ) -> Result<Node, AmiError> {
+        Err(ExprError::UnmatchedDelimiter {
+            opener,
+            expected_closer,
+            span: start..self.token.range.end,
+        }
+        .into())
     }
 
     fn skip_newlines(&mut self) -> u32 {
@@ -105,144 +283,86 @@ impl Parser {
             (Identifier(name), Eq) => {
                 self.advance();
                 self.advance();
-                let right = self.arith_expr()?;
+                let right = self.ternary()?;
                 self.node(NodeType::Assignment(name, Box::new(right)), start)
             }
-            _ => self.arith_expr(),
+            _ => self.ternary(),
         }
     }
 
-    fn arith_expr(&mut self) -> ParseResult {
+    /// `cond ? then : else`, the lowest-precedence construct in the
+    /// grammar — looser than `||` so `a || b ? c : d` parses as
+    /// `(a || b) ? c : d`. Right-associative: `a ? b : c ? d : e` parses as
+    /// `a ? b : (c ? d : e)`.
+    fn ternary(&mut self) -> ParseResult {
         let start = self.token.range.start;
-        let left = self.term()?;
+        let condition = self.expr_bp(TERNARY_BP)?;
 
-        match self.token.ty {
-            Plus => {
-                self.advance();
-                let right = self.arith_expr()?;
-                self.node(
-                    NodeType::Binary(Box::new(left), BinaryOp::Add, Box::new(right)),
-                    start,
-                )
-            }
-            Minus => {
-                self.advance();
-                let right = self.arith_expr()?;
-                self.node(
-                    NodeType::Binary(Box::new(left), BinaryOp::Sub, Box::new(right)),
-                    start,
-                )
-            }
-            _ => Ok(left),
+        if self.token.ty != Question {
+            return Ok(condition);
         }
-    }
+        self.advance();
 
-    fn term(&mut self) -> ParseResult {
-        let start = self.token.range.start;
-        let left = self.factor()?;
+        let then_branch = self.ternary()?;
 
-        match self.token.ty {
-            Star | Dot | Cross => {
-                self.advance();
-                let right = self.term()?;
-                self.node(
-                    NodeType::Binary(Box::new(left), BinaryOp::Mul, Box::new(right)),
-                    start,
-                )
-            }
-            Slash | Divide => {
-                self.advance();
-                let right = self.term()?;
-                self.node(
-                    NodeType::Binary(Box::new(left), BinaryOp::Div, Box::new(right)),
-                    start,
-                )
-            }
-            Percent | Mod => {
-                self.advance();
-                let right = self.term()?;
-                self.node(
-                    NodeType::Binary(Box::new(left), BinaryOp::Mod, Box::new(right)),
-                    start,
-                )
-            }
-            _ => Ok(left),
+        if self.token.ty != Colon {
+            return self.unexpected(vec![Colon.into()], start);
         }
-    }
+        self.advance();
 
-    fn factor(&mut self) -> ParseResult {
-        let start = self.token.range.start;
+        let else_branch = self.ternary()?;
 
-        match self.token.ty {
-            Plus => {
-                self.advance();
-                let right = self.factor()?;
-                self.node(NodeType::Unary(UnaryOp::Pos, Box::new(right)), start)
-            }
-            Minus => {
-                self.advance();
-                let right = self.factor()?;
-                self.node(NodeType::Unary(UnaryOp::Neg, Box::new(right)), start)
-            }
-            _ => self.power(),
-        }
+        self.node(
+            NodeType::Conditional(
+                Box::new(condition),
+                Box::new(then_branch),
+                Box::new(else_branch),
+            ),
+            start,
+        )
     }
 
-    fn power(&mut self) -> ParseResult {
+    /// Precedence-climbing (Pratt) driver: parses a prefix/atom, then
+    /// repeatedly consumes infix or postfix operators whose binding power
+    /// is at least `min_bp`, recursing with the operator's right binding
+    /// power for infixes. Replaces the old `arith_expr`/`term`/`factor`/
+    /// `power`/`prefix`/`postfix` chain with a single table-driven loop.
+    fn expr_bp(&mut self, min_bp: u8) -> ParseResult {
         let start = self.token.range.start;
-        let result = self.prefix()?;
 
-        match self.token.ty {
-            Carrot => {
+        let mut lhs = match prefix_binding_power(&self.token.ty) {
+            Some((op, bp)) => {
                 self.advance();
-                let exponent = self.factor()?;
-                self.node(
-                    NodeType::Binary(Box::new(result), BinaryOp::Pow, Box::new(exponent)),
-                    start,
-                )
+                let operand = self.expr_bp(bp)?;
+                self.node(NodeType::Unary(op, Box::new(operand)), start)?
             }
-            _ => Ok(result),
-        }
-    }
-
-    fn prefix(&mut self) -> ParseResult {
-        let start = self.token.range.start;
+            None => self.atom()?,
+        };
 
-        match self.token.ty {
-            Sqrt => {
-                self.advance();
-                let left = self.prefix()?;
-                self.node(NodeType::Unary(UnaryOp::Sqrt, Box::new(left)), start)
-            }
-            Cbrt => {
-                self.advance();
-                let left = self.prefix()?;
-                self.node(NodeType::Unary(UnaryOp::Cbrt, Box::new(left)), start)
-            }
-            Fort => {
+        loop {
+            if let Some((op, bp)) = postfix_binding_power(&self.token.ty) {
+                if bp < min_bp {
+                    break;
+                }
                 self.advance();
-                let left = self.prefix()?;
-                self.node(NodeType::Unary(UnaryOp::Fort, Box::new(left)), start)
+                lhs = self.node(NodeType::Unary(op, Box::new(lhs)), start)?;
+                continue;
             }
-            _ => self.postfix(),
-        }
-    }
-
-    fn postfix(&mut self) -> ParseResult {
-        let start = self.token.range.start;
-        let result = self.atom()?;
 
-        match self.token.ty {
-            Exclamation => {
-                self.advance();
-                self.node(NodeType::Unary(UnaryOp::Fact, Box::new(result)), start)
-            }
-            Degree => {
+            if let Some((op, left_bp, right_bp)) = infix_binding_power(&self.token.ty) {
+                if left_bp < min_bp {
+                    break;
+                }
                 self.advance();
-                self.node(NodeType::Unary(UnaryOp::Degree, Box::new(result)), start)
+                let rhs = self.expr_bp(right_bp)?;
+                lhs = self.node(NodeType::Binary(Box::new(lhs), op, Box::new(rhs)), start)?;
+                continue;
             }
-            _ => Ok(result),
+
+            break;
         }
+
+        Ok(lhs)
     }
 
     fn atom(&mut self) -> ParseResult {
@@ -255,18 +375,41 @@ impl Parser {
             }
             Identifier(name) => {
                 self.advance();
-                self.node(NodeType::Identifier(name), start)
+
+                if self.token.ty != LeftParen {
+                    return self.node(NodeType::Identifier(name), start);
+                }
+                self.advance();
+
+                let mut args = vec![];
+                if self.token.ty != RightParen {
+                    loop {
+                        args.push(self.ternary()?);
+
+                        if self.token.ty != Comma {
+                            break;
+                        }
+                        self.advance();
+                    }
+                }
+
+                if self.token.ty != RightParen {
+                    return self.unmatched_delimiter(LeftParen, RightParen, start);
+                }
+                let end = self.token.range.end;
+                self.advance();
+
+                Ok(Node {
+                    ty: NodeType::Call(name, args),
+                    range: start..end,
+                })
             }
             LeftParen => {
                 self.advance();
-                let result = self.arith_expr()?;
+                let result = self.ternary()?;
 
                 if self.token.ty != RightParen {
-                    return self.error(
-                        "expected token".to_string(),
-                        format!("expected {}", RightParen),
-                        start,
-                    );
+                    return self.unmatched_delimiter(LeftParen, RightParen, start);
                 }
                 self.advance();
 
@@ -274,14 +417,10 @@ impl Parser {
             }
             Pipe => {
                 self.advance();
-                let result = self.arith_expr()?;
+                let result = self.ternary()?;
 
                 if self.token.ty != Pipe {
-                    return self.error(
-                        "expected token".to_string(),
-                        format!("expected {}", Pipe),
-                        start,
-                    );
+                    return self.unmatched_delimiter(Pipe, Pipe, start);
                 }
                 self.advance();
 
@@ -289,46 +428,47 @@ impl Parser {
             }
             LeftFloor => {
                 self.advance();
-                let result = self.arith_expr()?;
+                let result = self.ternary()?;
 
                 match self.token.ty {
                     RightFloor => {
                         self.advance();
                         self.node(NodeType::Unary(UnaryOp::Floor, Box::new(result)), start)
                     }
+                    // ⌊x⌉ — floor-open, ceil-close — is the standard
+                    // round-to-nearest-integer bracket notation.
                     RightCeil => {
                         self.advance();
-                        self.node(NodeType::Unary(UnaryOp::Abs, Box::new(result)), start)
+                        self.node(NodeType::Unary(UnaryOp::Round, Box::new(result)), start)
                     }
-                    _ => self.error(
-                        "expected token".to_string(),
-                        format!("expected {} or {}", RightFloor, RightCeil),
-                        start,
-                    ),
+                    _ => self.unexpected(vec![RightFloor.into(), RightCeil.into()], start),
                 }
             }
             LeftCeil => {
                 self.advance();
-                let result = self.arith_expr()?;
-
-                if self.token.ty != RightCeil {
-                    return self.error(
-                        "expected token".to_string(),
-                        format!("expected {}", RightCeil),
-                        start,
-                    );
-                }
-                self.advance();
+                let result = self.ternary()?;
 
-                self.node(NodeType::Unary(UnaryOp::Ceil, Box::new(result)), start)
+                match self.token.ty {
+                    RightCeil => {
+                        self.advance();
+                        self.node(NodeType::Unary(UnaryOp::Ceil, Box::new(result)), start)
+                    }
+                    // ⌈x⌋ has no standard meaning, unlike the ⌊x⌉ round
+                    // bracket above — reject it rather than guessing.
+                    _ => self.unmatched_delimiter(LeftCeil, RightCeil, start),
+                }
             }
             EOF => self.node(NodeType::EOF, start),
-            _ => self.error(
-                "expected token".to_string(),
-                format!(
-                    "expected number, variable, function name, {}, {}, {}, or {}",
-                    LeftParen, Pipe, LeftFloor, LeftCeil
-                ),
+            _ => self.unexpected(
+                vec![
+                    Expected::Description("number"),
+                    Expected::Description("variable"),
+                    Expected::Description("function name"),
+                    LeftParen.into(),
+                    Pipe.into(),
+                    LeftFloor.into(),
+                    LeftCeil.into(),
+                ],
                 start,
             ),
         }